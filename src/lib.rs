@@ -9,23 +9,29 @@ use std::{
     fs::{self, File},
     io::Read,
     path::PathBuf,
+    process::Command,
+    result::Result as StdResult,
     string::ToString,
 };
 
 use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
 use clap::{App, Arg, ArgMatches};
 use console::{Emoji, Style};
 use dialoguer::{Confirm, Input, MultiSelect, Select};
 use globset::{Glob, GlobSetBuilder};
 use handlebars::Handlebars;
-use heck::KebabCase;
+use heck::{KebabCase, SnakeCase};
 use helpers::ForRangHelper;
 use indicatif::ProgressBar;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use toml::Value;
 use walkdir::WalkDir;
 
 const SCAFFOLD_FILENAME: &str = ".scaffold.toml";
+const TEMPLATE_EXTENSION: &str = "hbs";
+const PARTIALS_DIRNAME: &str = "partials";
 
 pub fn cli_init() -> Result<()> {
     let matches = App::new("cargo")
@@ -61,6 +67,21 @@ pub fn cli_init() -> Result<()> {
                         .long("passphrase")
                         .help("Specify if your SSH key is protected by a passphrase")
                         .takes_value(false),
+                    Arg::with_name("define")
+                        .short("D")
+                        .long("define")
+                        .help("Set a parameter value as key=value, skipping its prompt (can be repeated)")
+                        .takes_value(true)
+                        .number_of_values(1)
+                        .multiple(true),
+                    Arg::with_name("values-file")
+                        .long("values-file")
+                        .help("Specify a TOML file pre-seeding parameter values")
+                        .takes_value(true),
+                    Arg::with_name("non-interactive")
+                        .long("non-interactive")
+                        .help("Never prompt: error if a parameter has no value and no default")
+                        .takes_value(false),
                 ]),
         )
         .get_matches();
@@ -85,12 +106,32 @@ pub struct ScaffoldDescription {
     append: bool,
     #[serde(skip)]
     project_name: Option<String>,
+    #[serde(skip)]
+    predefined_values: BTreeMap<String, Value>,
+    #[serde(skip)]
+    non_interactive: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TemplateDescription {
     exclude: Option<Vec<String>>,
     notes: Option<String>,
+    hooks: Option<Hooks>,
+    conditional: Option<Vec<Conditional>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Conditional {
+    when: String,
+    globs: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Hooks {
+    #[serde(default)]
+    pre: Vec<String>,
+    #[serde(default)]
+    post: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -101,6 +142,9 @@ pub struct Parameter {
     r#type: ParameterType,
     default: Option<Value>,
     values: Option<Vec<Value>>,
+    regex: Option<String>,
+    #[serde(skip)]
+    compiled_regex: Option<Regex>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -128,9 +172,130 @@ pub struct Opts {
     pub append: bool,
     /// Specify if your SSH key is protected by a passphrase
     pub passphrase_needed: bool,
+    /// Pre-seed parameter values as `key=value`, skipping their prompt
+    pub defines: Vec<String>,
+    /// Pre-seed parameter values from a TOML file
+    pub values_file: Option<PathBuf>,
+    /// Never prompt: error if a parameter has no value and no default
+    pub non_interactive: bool,
+}
+
+/// Parses a `key=value` CLI argument into a parameter name and its TOML value, inferring the
+/// scalar type (boolean, integer, float, then string) from the value text.
+fn parse_define(define: &str) -> Result<(String, Value)> {
+    let (key, value) = define
+        .split_once('=')
+        .ok_or_else(|| anyhow!("invalid --define {}, expected key=value", define))?;
+
+    let value = if let Ok(b) = value.parse::<bool>() {
+        Value::Boolean(b)
+    } else if let Ok(i) = value.parse::<i64>() {
+        Value::Integer(i)
+    } else if let Ok(f) = value.parse::<f64>() {
+        Value::Float(f)
+    } else {
+        Value::String(value.to_string())
+    };
+
+    Ok((key.to_string(), value))
+}
+
+/// Builds the pre-seeded parameter map from a `--values-file` (applied first) and repeated
+/// `--define key=value` flags (applied last, so they override the file).
+fn predefined_values(
+    defines: &[String],
+    values_file: Option<&PathBuf>,
+) -> Result<BTreeMap<String, Value>> {
+    let mut values = BTreeMap::new();
+
+    if let Some(values_file) = values_file {
+        let content = fs::read_to_string(values_file).with_context(|| {
+            format!("cannot read values file {}", values_file.to_string_lossy())
+        })?;
+        values = toml::from_str(&content).with_context(|| {
+            format!("cannot parse values file {}", values_file.to_string_lossy())
+        })?;
+    }
+
+    for define in defines {
+        let (key, value) = parse_define(define)?;
+        values.insert(key, value);
+    }
+
+    Ok(values)
+}
+
+/// Reads a single `git config` value, e.g. `user.name` or `user.email`, returning `None` if git
+/// is not available, the key is unset, or the value is empty.
+fn git_config(key: &str) -> Option<String> {
+    let output = Command::new("git").args(&["config", "--get", key]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8(output.stdout).ok()?.trim().to_string();
+
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Derives an `authors` string in the style of `cargo new`. The name comes from `CARGO_NAME`,
+/// then `git config user.name`, then the `USER`/`USERNAME` env var (`Unknown` if none is set).
+/// The email is looked up on its own, from `CARGO_EMAIL` then `git config user.email`, and is
+/// left out when neither is set.
+fn detect_author() -> String {
+    let name = env::var("CARGO_NAME")
+        .ok()
+        .or_else(|| git_config("user.name"))
+        .or_else(|| env::var("USER").ok())
+        .or_else(|| env::var("USERNAME").ok())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    match env::var("CARGO_EMAIL").ok().or_else(|| git_config("user.email")) {
+        Some(email) => format!("{} <{}>", name, email),
+        None => name,
+    }
+}
+
+/// Peeks at the first few KB of a file to decide whether it is text or binary: a NUL byte or
+/// invalid UTF-8 in that sample means binary, so the file is copied verbatim instead of rendered.
+fn is_binary_file(path: &std::path::Path) -> Result<bool> {
+    let mut file = File::open(path).map_err(|e| anyhow!("cannot open file : {}", e))?;
+    let mut buffer = vec![0u8; 8192];
+    let read = file
+        .read(&mut buffer)
+        .map_err(|e| anyhow!("cannot read file : {}", e))?;
+    buffer.truncate(read);
+
+    // A multi-byte character can straddle the read boundary, so an incomplete
+    // sequence at the very end of the sample does not count as invalid UTF-8.
+    let invalid_utf8 = match std::str::from_utf8(&buffer) {
+        Ok(_) => false,
+        Err(e) => e.error_len().is_some(),
+    };
+    Ok(buffer.contains(&0) || invalid_utf8)
 }
 
 impl ScaffoldDescription {
+    /// Compiles each parameter's `regex` pattern once, so prompts only pay for matching and a
+    /// malformed pattern is reported up front instead of at prompt time.
+    fn compile_regexes(&mut self) -> Result<()> {
+        if let Some(parameters) = self.parameters.as_mut() {
+            for (name, parameter) in parameters.iter_mut() {
+                if let Some(pattern) = &parameter.regex {
+                    parameter.compiled_regex = Some(
+                        Regex::new(pattern)
+                            .with_context(|| format!("invalid regex for parameter {}", name))?,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn from_cli(matches: &ArgMatches) -> Result<Self> {
         let mut template_path = matches.value_of("template").unwrap().to_string();
         let mut scaffold_desc: ScaffoldDescription = {
@@ -156,6 +321,15 @@ impl ScaffoldDescription {
         scaffold_desc.project_name = matches.value_of("name").map(String::from);
         scaffold_desc.append = matches.is_present("append");
 
+        let defines: Vec<String> = matches
+            .values_of("define")
+            .map(|v| v.map(String::from).collect())
+            .unwrap_or_default();
+        let values_file = matches.value_of("values-file").map(PathBuf::from);
+        scaffold_desc.predefined_values = predefined_values(&defines, values_file.as_ref())?;
+        scaffold_desc.non_interactive = matches.is_present("non-interactive");
+        scaffold_desc.compile_regexes()?;
+
         Ok(scaffold_desc)
     }
 
@@ -185,6 +359,11 @@ impl ScaffoldDescription {
         scaffold_desc.project_name = opts.project_name;
         scaffold_desc.append = opts.append;
 
+        scaffold_desc.predefined_values =
+            predefined_values(&opts.defines, opts.values_file.as_ref())?;
+        scaffold_desc.non_interactive = opts.non_interactive;
+        scaffold_desc.compile_regexes()?;
+
         Ok(scaffold_desc)
     }
 
@@ -229,68 +408,153 @@ impl ScaffoldDescription {
         Ok(path)
     }
 
+    /// Checks a value against the parameter's compiled `regex`, the same way the interactive
+    /// `String`/`Integer` prompts do, so values coming from `--define`/`--values-file` or from
+    /// `--non-interactive` defaults can't bypass validation the interactive path would enforce.
+    fn validate_value_regex(parameter: &Parameter, parameter_name: &str, value: &Value) -> Result<()> {
+        let regex = match parameter.compiled_regex.as_ref() {
+            Some(regex) => regex,
+            None => return Ok(()),
+        };
+
+        let matches = match value {
+            Value::String(s) => s.is_empty() && !parameter.required || regex.is_match(s),
+            Value::Integer(i) => regex.is_match(&i.to_string()),
+            _ => true,
+        };
+
+        if matches {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "parameter {} value {} does not match required pattern /{}/",
+                parameter_name,
+                value,
+                regex.as_str()
+            ))
+        }
+    }
+
     fn fetch_parameters_value(&self) -> Result<BTreeMap<String, Value>> {
         let mut parameters: BTreeMap<String, Value> = BTreeMap::new();
 
         if let Some(parameter_list) = self.parameters.clone() {
             for (parameter_name, parameter) in parameter_list {
+                if let Some(predefined) = self.predefined_values.get(&parameter_name) {
+                    Self::validate_value_regex(&parameter, &parameter_name, predefined)?;
+                    parameters.insert(parameter_name, predefined.clone());
+                    continue;
+                }
+
+                if self.non_interactive {
+                    let value = parameter.default.clone().ok_or_else(|| {
+                        anyhow!(
+                            "parameter {} has no value and no default (--non-interactive)",
+                            parameter_name
+                        )
+                    })?;
+                    Self::validate_value_regex(&parameter, &parameter_name, &value)?;
+                    parameters.insert(parameter_name, value);
+                    continue;
+                }
+
                 let value: Value = match parameter.r#type {
                     ParameterType::String => {
-                        Value::String(Input::new().with_prompt(parameter.message).interact()?)
+                        let mut input = Input::new()
+                            .with_prompt(parameter.message.clone())
+                            .allow_empty(!parameter.required);
+                        if let Some(default) = parameter.default.as_ref().and_then(Value::as_str) {
+                            input = input.default(default.to_string());
+                        }
+                        if let Some(regex) = parameter.compiled_regex.clone() {
+                            let hint =
+                                format!("{} (must match /{}/)", parameter.message, regex.as_str());
+                            input = input.validate_with(
+                                move |value: &String| -> StdResult<(), String> {
+                                    if value.is_empty() || regex.is_match(value) {
+                                        Ok(())
+                                    } else {
+                                        Err(hint.clone())
+                                    }
+                                },
+                            );
+                        }
+                        Value::String(input.interact()?)
+                    }
+                    ParameterType::Float => {
+                        let mut input = Input::<f64>::new().with_prompt(parameter.message.clone());
+                        if let Some(default) = parameter.default.as_ref().and_then(Value::as_float)
+                        {
+                            input = input.default(default);
+                        }
+                        Value::Float(input.interact()?)
+                    }
+                    ParameterType::Integer => {
+                        let mut input = Input::<i64>::new().with_prompt(parameter.message.clone());
+                        if let Some(default) =
+                            parameter.default.as_ref().and_then(Value::as_integer)
+                        {
+                            input = input.default(default);
+                        }
+                        if let Some(regex) = parameter.compiled_regex.clone() {
+                            let hint =
+                                format!("{} (must match /{}/)", parameter.message, regex.as_str());
+                            input = input.validate_with(
+                                move |value: &i64| -> StdResult<(), String> {
+                                    if regex.is_match(&value.to_string()) {
+                                        Ok(())
+                                    } else {
+                                        Err(hint.clone())
+                                    }
+                                },
+                            );
+                        }
+                        Value::Integer(input.interact()?)
                     }
-                    ParameterType::Float => Value::Float(
-                        Input::<f64>::new()
-                            .with_prompt(parameter.message)
-                            .interact()?,
-                    ),
-                    ParameterType::Integer => Value::Integer(
-                        Input::<i64>::new()
-                            .with_prompt(parameter.message)
-                            .interact()?,
-                    ),
                     ParameterType::Boolean => {
-                        Value::Boolean(Confirm::new().with_prompt(parameter.message).interact()?)
+                        let mut confirm = Confirm::new().with_prompt(parameter.message.clone());
+                        if let Some(default) = parameter.default.as_ref().and_then(Value::as_bool)
+                        {
+                            confirm = confirm.default(default);
+                        }
+                        Value::Boolean(confirm.interact()?)
                     }
                     ParameterType::Select => {
-                        let idx_selected = Select::new()
-                            .items(
-                                parameter
-                                    .values
-                                    .as_ref()
-                                    .expect("cannot make a select parameter with empty values"),
-                            )
-                            .with_prompt(parameter.message)
-                            .default(0)
-                            .interact()?;
-                        parameter
+                        let values = parameter
                             .values
                             .as_ref()
-                            .expect("cannot make a select parameter with empty values")
-                            .get(idx_selected)
-                            .unwrap()
-                            .clone()
+                            .expect("cannot make a select parameter with empty values");
+                        let default_idx = parameter
+                            .default
+                            .as_ref()
+                            .and_then(|default| values.iter().position(|v| v == default))
+                            .unwrap_or(0);
+                        let idx_selected = Select::new()
+                            .items(values)
+                            .with_prompt(parameter.message.clone())
+                            .default(default_idx)
+                            .interact()?;
+                        values.get(idx_selected).unwrap().clone()
                     }
                     ParameterType::MultiSelect => {
+                        let values = parameter
+                            .values
+                            .as_ref()
+                            .expect("cannot make a select parameter with empty values");
+                        let defaults: Vec<bool> = match parameter.default.as_ref() {
+                            Some(Value::Array(defaults)) => {
+                                values.iter().map(|v| defaults.contains(v)).collect()
+                            }
+                            _ => vec![false; values.len()],
+                        };
                         let idxs_selected = MultiSelect::new()
-                            .items(
-                                parameter
-                                    .values
-                                    .as_ref()
-                                    .expect("cannot make a select parameter with empty values"),
-                            )
+                            .items(values)
                             .with_prompt(parameter.message.clone())
+                            .defaults(&defaults)
                             .interact()?;
                         let values = idxs_selected
                             .into_iter()
-                            .map(|idx| {
-                                parameter
-                                    .values
-                                    .as_ref()
-                                    .expect("cannot make a select parameter with empty values")
-                                    .get(idx)
-                                    .unwrap()
-                                    .clone()
-                            })
+                            .map(|idx| values.get(idx).unwrap().clone())
                             .collect();
 
                         Value::Array(values)
@@ -303,18 +567,126 @@ impl ScaffoldDescription {
         Ok(parameters)
     }
 
-    pub fn scaffold(&self) -> Result<()> {
-        let excludes = match &self.template.exclude {
-            Some(exclude) => {
-                let mut builder = GlobSetBuilder::new();
-                for ex in exclude {
-                    builder.add(Glob::new(ex)?);
-                }
+    /// Runs the author-defined hook scripts for a given phase (`pre` or `post`), with the
+    /// generated project directory as CWD. Each hook receives the collected `parameters` both
+    /// as a `SCAFFOLD_PARAMETERS` JSON env var and as a `SCAFFOLD_PARAMETERS_FILE` on disk; a
+    /// pre hook can rewrite that file to compute additional values, which are merged back into
+    /// `parameters` before the next hook runs (and before templating starts). Returns an error
+    /// if any hook exits non-zero.
+    fn run_hooks(
+        &self,
+        hooks: &[String],
+        dir_path: &std::path::Path,
+        parameters: &mut BTreeMap<String, Value>,
+    ) -> Result<()> {
+        if hooks.is_empty() {
+            return Ok(());
+        }
+
+        let params_file = dir_path.join(".scaffold-parameters.json");
+        let result = self.run_hooks_inner(hooks, dir_path, &params_file, parameters);
+        fs::remove_file(&params_file).ok();
 
-                builder.build()?
+        result
+    }
+
+    /// Does the actual work for `run_hooks`, split out so the caller can unconditionally clean
+    /// up the parameters file regardless of whether a hook failed.
+    fn run_hooks_inner(
+        &self,
+        hooks: &[String],
+        dir_path: &std::path::Path,
+        params_file: &std::path::Path,
+        parameters: &mut BTreeMap<String, Value>,
+    ) -> Result<()> {
+        for hook in hooks {
+            let hook_path = self.template_path.join(hook);
+            let params_json = serde_json::to_string(&*parameters)
+                .with_context(|| "cannot serialize parameters for hooks")?;
+            fs::write(params_file, &params_json)
+                .with_context(|| "cannot write parameters file for hooks")?;
+
+            let status = Command::new(&hook_path)
+                .current_dir(dir_path)
+                .env("SCAFFOLD_PARAMETERS", &params_json)
+                .env("SCAFFOLD_PARAMETERS_FILE", params_file)
+                .status()
+                .with_context(|| {
+                    format!(
+                        "cannot run hook {} (it must be executable, e.g. `chmod +x` and start with a #! shebang such as #!/usr/bin/env bash)",
+                        hook
+                    )
+                })?;
+
+            if !status.success() {
+                return Err(anyhow!("hook {} exited with status {}", hook, status));
             }
-            None => GlobSetBuilder::new().build()?,
-        };
+
+            let updated_params = fs::read_to_string(params_file).with_context(|| {
+                format!("cannot read back parameters file after hook {}", hook)
+            })?;
+            let updated_params: BTreeMap<String, Value> = serde_json::from_str(&updated_params)
+                .with_context(|| format!("hook {} wrote invalid parameters JSON", hook))?;
+            parameters.extend(updated_params);
+        }
+
+        Ok(())
+    }
+
+    /// Registers every file under the template's `partials/` directory as a Handlebars partial,
+    /// keyed by its path relative to that directory with any `.hbs` extension stripped, so
+    /// templates can share snippets (license headers, CI fragments) via `{{> header}}`.
+    fn register_partials(&self, template_engine: &mut Handlebars) -> Result<()> {
+        let partials_dir = self.template_path.join(PARTIALS_DIRNAME);
+        if !partials_dir.exists() {
+            return Ok(());
+        }
+
+        for entry in WalkDir::new(&partials_dir) {
+            let entry = entry.map_err(|e| anyhow!("cannot read entry : {}", e))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let name = entry
+                .path()
+                .strip_prefix(&partials_dir)?
+                .to_str()
+                .expect("path is not utf8 valid")
+                .trim_end_matches(&format!(".{}", TEMPLATE_EXTENSION))
+                .to_string();
+
+            let mut content = String::new();
+            File::open(entry.path())
+                .map_err(|e| anyhow!("cannot open file : {}", e))?
+                .read_to_string(&mut content)
+                .map_err(|e| anyhow!("cannot read file : {}", e))?;
+
+            template_engine
+                .register_partial(&name, content)
+                .map_err(|e| anyhow!("cannot register partial {} : {}", name, e))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn scaffold(&self) -> Result<()> {
+        let hooks = self.template.hooks.clone().unwrap_or_default();
+
+        let mut exclude_globs: Vec<String> = self.template.exclude.clone().unwrap_or_default();
+        exclude_globs.extend(hooks.pre.iter().chain(hooks.post.iter()).cloned());
+        exclude_globs.push(format!("{}/**", PARTIALS_DIRNAME));
+        exclude_globs.push(PARTIALS_DIRNAME.to_string());
+
+        let mut template_engine = Handlebars::new();
+        handlebars_misc_helpers::setup_handlebars(&mut template_engine);
+        template_engine.register_helper("forRange", Box::new(ForRangHelper));
+        template_engine.set_strict_mode(true);
+        self.register_partials(&mut template_engine)?;
+
+        if self.non_interactive && self.project_name.is_none() {
+            return Err(anyhow!("project has no name (--non-interactive), set it with --name"));
+        }
 
         let mut parameters: BTreeMap<String, Value> = self.fetch_parameters_value()?;
         let name: String = match &self.project_name {
@@ -330,6 +702,55 @@ impl ScaffoldDescription {
         );
 
         parameters.insert("name".to_string(), Value::String(name.clone()));
+        parameters.insert(
+            "crate_name".to_string(),
+            Value::String(name.to_snake_case()),
+        );
+        parameters.insert(
+            "project_name".to_string(),
+            Value::String(name.to_kebab_case()),
+        );
+        parameters.insert("authors".to_string(), Value::String(detect_author()));
+        parameters.insert(
+            "date".to_string(),
+            Value::String(Utc::now().format("%Y-%m-%d").to_string()),
+        );
+        parameters.insert(
+            "year".to_string(),
+            Value::String(Utc::now().format("%Y").to_string()),
+        );
+
+        // Pre-hooks run before conditionals are evaluated and before the exclude `GlobSet` is
+        // frozen, so a pre-hook's write-back values can actually influence which files are
+        // conditionally included, matching the parameters used to render them.
+        self.run_hooks(&hooks.pre, &dir_path, &mut parameters)
+            .with_context(|| "pre-generation hook failed")?;
+
+        if let Some(conditionals) = &self.template.conditional {
+            for conditional in conditionals {
+                let rendered = template_engine
+                    .render_template(&conditional.when, &parameters)
+                    .with_context(|| format!("cannot evaluate condition {}", conditional.when))?;
+                if rendered.trim() != "true" {
+                    for glob in &conditional.globs {
+                        // A `dir/**` glob excludes the directory's contents but not the
+                        // directory entry itself, which would otherwise still get created
+                        // empty; exclude the bare directory too, as for `partials/` above.
+                        if let Some(dir_glob) = glob.strip_suffix("/**") {
+                            exclude_globs.push(dir_glob.to_string());
+                        }
+                        exclude_globs.push(glob.clone());
+                    }
+                }
+            }
+        }
+
+        let mut excludes_builder = GlobSetBuilder::new();
+        for exclude_glob in &exclude_globs {
+            excludes_builder.add(Glob::new(exclude_glob)?);
+        }
+        let excludes = excludes_builder.build()?;
+
         // List entries inside directory
         let entries = WalkDir::new(&self.template_path)
             .into_iter()
@@ -355,10 +776,6 @@ impl ScaffoldDescription {
                 )
             });
 
-        let mut template_engine = Handlebars::new();
-        handlebars_misc_helpers::setup_handlebars(&mut template_engine);
-        template_engine.register_helper("forRange", Box::new(ForRangHelper));
-
         let cyan = Style::new().cyan();
         println!("{} {}", Emoji("🔄", ""), cyan.apply_to("Templating files…"),);
         for entry in entries {
@@ -377,6 +794,31 @@ impl ScaffoldDescription {
             }
 
             let filename = entry.path();
+            let is_template =
+                filename.extension().and_then(|ext| ext.to_str()) == Some(TEMPLATE_EXTENSION);
+
+            let mut rendered_path = template_engine
+                .render_template(
+                    dir_path
+                        .join(entry_path)
+                        .to_str()
+                        .expect("path is not utf8 valid"),
+                    &parameters,
+                )
+                .map_err(|e| anyhow!("cannot render template for path : {}", e))?;
+            if is_template {
+                rendered_path = rendered_path
+                    .strip_suffix(&format!(".{}", TEMPLATE_EXTENSION))
+                    .unwrap_or(&rendered_path)
+                    .to_string();
+            }
+
+            if is_binary_file(filename)? {
+                fs::copy(filename, rendered_path)
+                    .map_err(|e| anyhow!("cannot copy file : {}", e))?;
+                continue;
+            }
+
             let mut content = String::new();
             {
                 let mut file =
@@ -384,23 +826,24 @@ impl ScaffoldDescription {
                 file.read_to_string(&mut content)
                     .map_err(|e| anyhow!("cannot read file : {}", e))?;
             }
+
+            if !is_template {
+                fs::write(rendered_path, content)
+                    .map_err(|e| anyhow!("cannot create file : {}", e))?;
+                continue;
+            }
+
             let rendered_content = template_engine
                 .render_template(&content, &parameters)
                 .map_err(|e| anyhow!("cannot render template : {}", e))?;
-            let rendered_path = template_engine
-                .render_template(
-                    dir_path
-                        .join(entry_path)
-                        .to_str()
-                        .expect("path is not utf8 valid"),
-                    &parameters,
-                )
-                .map_err(|e| anyhow!("cannot render template for path : {}", e))?;
 
             fs::write(rendered_path, rendered_content)
                 .map_err(|e| anyhow!("cannot create file : {}", e))?;
         }
 
+        self.run_hooks(&hooks.post, &dir_path, &mut parameters)
+            .with_context(|| "post-generation hook failed")?;
+
         let green = Style::new().green();
         println!(
             "{} Your project {} has been generated successfuly {}",
@@ -429,3 +872,64 @@ impl ScaffoldDescription {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_binary_file_accepts_utf8_straddling_the_read_boundary() {
+        // A multi-byte character whose first byte falls at the very end of the
+        // 8192-byte read buffer used to make a valid UTF-8 text file look binary.
+        let mut content = vec![b'a'; 8191];
+        content.extend_from_slice("é".as_bytes());
+
+        let path = env::temp_dir().join(format!(
+            "cargo-scaffold-test-{}-{}.txt",
+            std::process::id(),
+            line!()
+        ));
+        fs::write(&path, &content).unwrap();
+
+        let result = is_binary_file(&path);
+        fs::remove_file(&path).ok();
+
+        assert!(!result.unwrap());
+    }
+
+    #[test]
+    fn parse_define_infers_scalar_types() {
+        assert_eq!(
+            parse_define("enabled=true").unwrap(),
+            ("enabled".to_string(), Value::Boolean(true))
+        );
+        assert_eq!(
+            parse_define("count=42").unwrap(),
+            ("count".to_string(), Value::Integer(42))
+        );
+        assert_eq!(
+            parse_define("ratio=1.5").unwrap(),
+            ("ratio".to_string(), Value::Float(1.5))
+        );
+        assert_eq!(
+            parse_define("name=cargo-scaffold").unwrap(),
+            ("name".to_string(), Value::String("cargo-scaffold".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_define_requires_an_equals_sign() {
+        assert!(parse_define("no-equals-sign").is_err());
+    }
+
+    #[test]
+    fn parse_define_keeps_only_first_equals_split() {
+        assert_eq!(
+            parse_define("url=https://example.com/a=b").unwrap(),
+            (
+                "url".to_string(),
+                Value::String("https://example.com/a=b".to_string())
+            )
+        );
+    }
+}